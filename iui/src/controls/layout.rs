@@ -1,4 +1,4 @@
-use super::Control;
+use super::{Control, Label};
 use error::UIError;
 use libc::c_int;
 use std::ffi::{CStr, CString};
@@ -14,60 +14,181 @@ pub enum LayoutStrategy {
     Stretchy,
 }
 
-define_control! {
-    /// Lays out its children vertically; see [`BoxExt`](trait.BoxExt.html) for functionality.
-    ///
-    /// # Example
-    ///
-    /// This example will lay out two labels, one atop the other.
-    ///
-    /// ```
-    /// # use iui::prelude::*;
-    /// # use iui::controls::{Label, VerticalBox};
-    /// # let ui = UI::init().expect("Couldn't initialize UI library");
-    /// # let mut win = Window::new(&ui, "Test App", 200, 200, WindowType::NoMenubar);
-    /// // Create a vertical layout
-    /// let mut vbox = VerticalBox::new(&ui);
-    /// // Create two controls to place in the layout
-    /// let label1 = Label::new(&ui, "On Top");
-    /// let label2 = Label::new(&ui, "Underneath");
-    /// // Add the controls to the layout
-    /// vbox.add(&ui, label1);
-    /// vbox.add(&ui, label2);
-    /// # win.set_child(&ui, vbox);
-    /// # ui.quit();
-    /// # ui.main();
-    /// ```
-    rust_type: VerticalBox,
-    sys_type: uiBox
+/// A sizing constraint for a child added to a box, offering finer intent than the
+/// binary [`LayoutStrategy`](enum.LayoutStrategy.html).
+///
+/// libui's `uiBoxAppend` only exposes a boolean "stretchy" flag, which cannot
+/// express relative proportions between several children. To honour these
+/// constraints, `add_with_constraint` instead backs the constrained children with
+/// an internal [`LayoutGrid`](struct.LayoutGrid.html): each child is given a column
+/// (horizontal box) or row (vertical box) *span* proportional to its weight, and
+/// the weighted children are set to expand. Because every grid track is equal
+/// width, a child spanning twice as many tracks receives twice the space, which
+/// gives genuine proportional sizing that the bare box cannot.
+///
+/// These sizes are resolved *relative to the other constrained children*, not
+/// against an absolute pixel extent: libui's immediate box/grid model never
+/// exposes the parent's size to resolve against, so `Fixed`/`Min` cannot be pinned
+/// to their exact pixel value (they fall back to the child's natural, compact size)
+/// and `Percent`/`Ratio` express a share relative to the sibling weights rather
+/// than of the parent. This is weaker than a pixel-exact layout engine, but is the
+/// strongest proportional sizing the toolkit can deliver through this wrapper.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Constraint {
+    /// Lay the child out at its natural, compact size. The pixel value is advisory:
+    /// libui cannot pin a box child to an exact pixel extent.
+    Fixed(u32),
+    /// Give the child a share of the space proportional to this percentage, relative
+    /// to the other weighted children in the same box (not of the parent).
+    Percent(u8),
+    /// Give the child a share of the space proportional to `numerator / denominator`,
+    /// relative to the other weighted children in the same box (not of the parent).
+    Ratio(u32, u32),
+    /// Lay the child out at its natural, compact size, at least as large as its
+    /// content. As with `Fixed`, the pixel value is advisory.
+    Min(u32),
+    /// Share leftover space with other weighted children according to this weight;
+    /// `Fill(0)` takes no extra space.
+    Fill(u16),
 }
 
-define_control! {
-    /// Lays out its children horizontally; see [`BoxExt`](trait.BoxExt.html) for functionality.
-    ///
-    /// # Example
-    ///
-    /// This example will lay out two labels beside each other.
-    ///
-    /// ```
-    /// # use iui::prelude::*;
-    /// # use iui::controls::{Label, HorizontalBox};
-    /// # let ui = UI::init().expect("Couldn't initialize UI library");
-    /// # let mut win = Window::new(&ui, "Test App", 200, 200, WindowType::NoMenubar);
-    /// // Create a horizontal layout
-    /// let mut vbox = HorizontalBox::new(&ui);
-    /// // Create two controls to place in the layout
-    /// let label1 = Label::new(&ui, "Left");
-    /// let label2 = Label::new(&ui, "Right");
-    /// // Add the controls to the layout
-    /// vbox.add(&ui, label1);
-    /// vbox.add(&ui, label2);
-    /// # win.set_child(&ui, vbox);
-    /// # ui.quit();
-    /// # ui.main();
-    /// ```
-    rust_type: HorizontalBox,
-    sys_type: uiBox
+impl Constraint {
+    /// Largest grid span a single child may occupy, bounding the backing grid's
+    /// size for pathological weights.
+    const MAX_SPAN: i64 = 1000;
+
+    /// The number of grid tracks this constraint occupies. Weighted children span a
+    /// number of tracks proportional to their weight so they share space in the
+    /// requested ratios; compact children (`Fixed`, `Min`, `Fill(0)`) occupy a
+    /// single track, since their pixel value cannot map onto grid tracks.
+    fn weight(&self) -> i32 {
+        let raw: i64 = match *self {
+            Constraint::Fixed(_) | Constraint::Min(_) | Constraint::Fill(0) => 1,
+            Constraint::Percent(p) => p as i64,
+            Constraint::Fill(w) => w as i64,
+            Constraint::Ratio(num, den) => {
+                if den == 0 {
+                    1
+                } else {
+                    num as i64 * 100 / den as i64
+                }
+            }
+        };
+        raw.max(1).min(Self::MAX_SPAN) as i32
+    }
+
+    /// Whether this constraint shares leftover space (and so should expand within
+    /// its grid span) rather than being laid out compactly.
+    fn is_weighted(&self) -> bool {
+        match *self {
+            Constraint::Fixed(_) | Constraint::Min(_) | Constraint::Fill(0) => false,
+            Constraint::Fill(_) | Constraint::Percent(_) | Constraint::Ratio(_, _) => true,
+        }
+    }
+}
+
+/// Lays out its children vertically; see [`BoxExt`](trait.BoxExt.html) for functionality.
+///
+/// # Example
+///
+/// This example will lay out two labels, one atop the other.
+///
+/// ```
+/// # use iui::prelude::*;
+/// # use iui::controls::{Label, VerticalBox};
+/// # let ui = UI::init().expect("Couldn't initialize UI library");
+/// # let mut win = Window::new(&ui, "Test App", 200, 200, WindowType::NoMenubar);
+/// // Create a vertical layout
+/// let mut vbox = VerticalBox::new(&ui);
+/// // Create two controls to place in the layout
+/// let label1 = Label::new(&ui, "On Top");
+/// let label2 = Label::new(&ui, "Underneath");
+/// // Add the controls to the layout
+/// vbox.add(&ui, label1);
+/// vbox.add(&ui, label2);
+/// # win.set_child(&ui, vbox);
+/// # ui.quit();
+/// # ui.main();
+/// ```
+pub struct VerticalBox {
+    uiBox: *mut uiBox,
+    /// The controls this box owns, in insertion order, so they can be deleted
+    /// without leaking.
+    children: Vec<Control>,
+    /// Backing grid for children added with a [`Constraint`](enum.Constraint.html),
+    /// created on first use, along with the next free row offset within it.
+    constraint_grid: Option<LayoutGrid>,
+    constraint_offset: i32,
+}
+
+/// Lays out its children horizontally; see [`BoxExt`](trait.BoxExt.html) for functionality.
+///
+/// # Example
+///
+/// This example will lay out two labels beside each other.
+///
+/// ```
+/// # use iui::prelude::*;
+/// # use iui::controls::{Label, HorizontalBox};
+/// # let ui = UI::init().expect("Couldn't initialize UI library");
+/// # let mut win = Window::new(&ui, "Test App", 200, 200, WindowType::NoMenubar);
+/// // Create a horizontal layout
+/// let mut vbox = HorizontalBox::new(&ui);
+/// // Create two controls to place in the layout
+/// let label1 = Label::new(&ui, "Left");
+/// let label2 = Label::new(&ui, "Right");
+/// // Add the controls to the layout
+/// vbox.add(&ui, label1);
+/// vbox.add(&ui, label2);
+/// # win.set_child(&ui, vbox);
+/// # ui.quit();
+/// # ui.main();
+/// ```
+pub struct HorizontalBox {
+    uiBox: *mut uiBox,
+    /// The controls this box owns, in insertion order, so they can be deleted
+    /// without leaking.
+    children: Vec<Control>,
+    /// Backing grid for children added with a [`Constraint`](enum.Constraint.html),
+    /// created on first use, along with the next free column offset within it.
+    constraint_grid: Option<LayoutGrid>,
+    constraint_offset: i32,
+}
+
+impl VerticalBox {
+    /// Create a `VerticalBox` from a raw pointer to the underlying `uiBox`.
+    pub unsafe fn from_raw(raw: *mut uiBox) -> VerticalBox {
+        VerticalBox {
+            uiBox: raw,
+            children: Vec::new(),
+            constraint_grid: None,
+            constraint_offset: 0,
+        }
+    }
+}
+
+impl HorizontalBox {
+    /// Create a `HorizontalBox` from a raw pointer to the underlying `uiBox`.
+    pub unsafe fn from_raw(raw: *mut uiBox) -> HorizontalBox {
+        HorizontalBox {
+            uiBox: raw,
+            children: Vec::new(),
+            constraint_grid: None,
+            constraint_offset: 0,
+        }
+    }
+}
+
+impl From<VerticalBox> for Control {
+    fn from(b: VerticalBox) -> Control {
+        unsafe { Control::from_ui_control(b.uiBox as *mut uiControl) }
+    }
+}
+
+impl From<HorizontalBox> for Control {
+    fn from(b: HorizontalBox) -> Control {
+        unsafe { Control::from_ui_control(b.uiBox as *mut uiControl) }
+    }
 }
 
 impl VerticalBox {
@@ -75,6 +196,9 @@ impl VerticalBox {
     pub fn new(_ctx: &UI) -> VerticalBox {
         VerticalBox {
             uiBox: unsafe { ui_sys::uiNewVerticalBox() },
+            children: Vec::new(),
+            constraint_grid: None,
+            constraint_offset: 0,
         }
     }
 }
@@ -84,11 +208,19 @@ impl HorizontalBox {
     pub fn new(_ctx: &UI) -> HorizontalBox {
         HorizontalBox {
             uiBox: unsafe { ui_sys::uiNewHorizontalBox() },
+            children: Vec::new(),
+            constraint_grid: None,
+            constraint_offset: 0,
         }
     }
 }
 
-fn append<T: Into<Control>>(b: *mut uiBox, ctx: &UI, child: T, strategy: LayoutStrategy) {
+fn append<T: Into<Control>>(
+    b: *mut uiBox,
+    ctx: &UI,
+    child: T,
+    strategy: LayoutStrategy,
+) -> Control {
     let stretchy = match strategy {
         LayoutStrategy::Compact => false,
         LayoutStrategy::Stretchy => true,
@@ -98,6 +230,7 @@ fn append<T: Into<Control>>(b: *mut uiBox, ctx: &UI, child: T, strategy: LayoutS
         assert!(ctx.parent_of(control.clone()).is_none());
         ui_sys::uiBoxAppend(b, control.ui_control, stretchy as c_int)
     }
+    control
 }
 
 fn padded(b: *mut uiBox, _ctx: &UI) -> bool {
@@ -111,18 +244,101 @@ fn set_padded(b: *mut uiBox, padded: bool, _ctx: &UI) {
 impl VerticalBox {
     /// Add a control to the next position in the box, using the minimum possible space.
     pub fn add<T: Into<Control>>(&mut self, _ctx: &UI, child: T) {
-        self.append(_ctx, child, LayoutStrategy::Compact);
+        let control = append(self.uiBox, _ctx, child, LayoutStrategy::Compact);
+        self.children.push(control);
     }
 
     /// Add a control to the next position in the box, expanding to use all available space.
     pub fn add_stretchy<T: Into<Control>>(&mut self, _ctx: &UI, child: T) {
-        self.append(_ctx, child, LayoutStrategy::Stretchy);
+        let control = append(self.uiBox, _ctx, child, LayoutStrategy::Stretchy);
+        self.children.push(control);
     }
 
     /// Add a control to the end of the box, sized by the given layout strategy.
     #[deprecated(since = "0.4.0", note = "Use one of `add` or `add_stretchy` instead.")]
     pub fn append<T: Into<Control>>(&mut self, _ctx: &UI, child: T, strategy: LayoutStrategy) {
-        append(self.uiBox, _ctx, child, strategy)
+        let control = append(self.uiBox, _ctx, child, strategy);
+        self.children.push(control);
+    }
+
+    /// Add a control to the next position in the box, sized according to the given
+    /// [`Constraint`](enum.Constraint.html).
+    ///
+    /// Constrained children are laid out in an internal grid (created on first use
+    /// and appended to the box as a single stretchy child), each occupying a number
+    /// of rows proportional to its weight so that weighted children share space in
+    /// the requested proportions. See [`Constraint`](enum.Constraint.html) for the
+    /// exact resolution.
+    ///
+    /// Because every constrained child goes into that one grid, which is appended at
+    /// the position of the first constrained add, constrained and unconstrained adds
+    /// should not be interleaved: do all of a box's `add`/`add_stretchy` calls and
+    /// all of its `add_with_constraint` calls as two separate groups, or the
+    /// constrained children will render out of order relative to the plain ones.
+    pub fn add_with_constraint<T: Into<Control>>(
+        &mut self,
+        ctx: &UI,
+        child: T,
+        constraint: Constraint,
+    ) {
+        if self.constraint_grid.is_none() {
+            let mut grid = LayoutGrid::new(ctx);
+            grid.set_padded(ctx, true);
+            let grid_control =
+                unsafe { Control::from_ui_control(grid.uiGrid as *mut uiControl) };
+            unsafe {
+                assert!(ctx.parent_of(grid_control.clone()).is_none());
+                ui_sys::uiBoxAppend(self.uiBox, grid_control.ui_control, true as c_int);
+            }
+            // Track the backing grid like any other child so `delete` indices stay
+            // aligned with the box's real child list.
+            self.children.push(grid_control);
+            self.constraint_grid = Some(grid);
+        }
+        let weight = constraint.weight();
+        let expand = if constraint.is_weighted() {
+            GridExpand::Vertical
+        } else {
+            GridExpand::Neither
+        };
+        let top = self.constraint_offset;
+        self.constraint_offset += weight;
+        let grid = self.constraint_grid.as_mut().unwrap();
+        grid.append(
+            ctx,
+            child,
+            0,
+            top,
+            1,
+            weight,
+            expand,
+            GridAlignment::Fill,
+            GridAlignment::Fill,
+        );
+    }
+
+    /// Remove and destroy the control at the given index in the box.
+    ///
+    /// Unlike the underlying `uiBoxDelete`, which only unparents the control, this
+    /// also drops the box's owned handle and destroys the control, releasing its
+    /// memory. Panics if `index` is out of range.
+    pub fn delete(&mut self, _ctx: &UI, index: usize) {
+        let control = self.children.remove(index);
+        // If the control being destroyed is the backing constraint grid, forget it
+        // so a later `add_with_constraint` builds a fresh one rather than appending
+        // into freed memory.
+        if self
+            .constraint_grid
+            .as_ref()
+            .map_or(false, |grid| grid.uiGrid as *mut uiControl == control.ui_control)
+        {
+            self.constraint_grid = None;
+            self.constraint_offset = 0;
+        }
+        unsafe {
+            ui_sys::uiBoxDelete(self.uiBox, index as c_int);
+            ui_sys::uiControlDestroy(control.ui_control);
+        }
     }
 
     /// Determine whenther the box provides padding around its children.
@@ -134,23 +350,150 @@ impl VerticalBox {
     pub fn set_padded(&mut self, _ctx: &UI, padded: bool) {
         set_padded(self.uiBox, padded, _ctx)
     }
+
+    /// Create a nested vertical box, populate it with the given closure, then add
+    /// it to this box using the given layout strategy.
+    ///
+    /// This lets callers build nested layouts without creating each child box,
+    /// threading `&ui` through it, and calling `add` by hand.
+    pub fn vertical<F: FnOnce(&mut VerticalBox)>(
+        &mut self,
+        ctx: &UI,
+        strategy: LayoutStrategy,
+        f: F,
+    ) {
+        let mut child = VerticalBox::new(ctx);
+        f(&mut child);
+        let control = append(self.uiBox, ctx, child, strategy);
+        self.children.push(control);
+    }
+
+    /// Create a nested horizontal box, populate it with the given closure, then add
+    /// it to this box using the given layout strategy.
+    pub fn horizontal<F: FnOnce(&mut HorizontalBox)>(
+        &mut self,
+        ctx: &UI,
+        strategy: LayoutStrategy,
+        f: F,
+    ) {
+        let mut child = HorizontalBox::new(ctx);
+        f(&mut child);
+        let control = append(self.uiBox, ctx, child, strategy);
+        self.children.push(control);
+    }
+
+    /// Construct a control with the given closure and add it to the box using the
+    /// given layout strategy.
+    pub fn add_with<T: Into<Control>, F: FnOnce(&UI) -> T>(
+        &mut self,
+        ctx: &UI,
+        strategy: LayoutStrategy,
+        f: F,
+    ) {
+        let child = f(ctx);
+        let control = append(self.uiBox, ctx, child, strategy);
+        self.children.push(control);
+    }
 }
 
 impl HorizontalBox {
     /// Add a control to the next position in the box, using the minimum possible space.
     pub fn add<T: Into<Control>>(&mut self, _ctx: &UI, child: T) {
-        self.append(_ctx, child, LayoutStrategy::Compact);
+        let control = append(self.uiBox, _ctx, child, LayoutStrategy::Compact);
+        self.children.push(control);
     }
 
     /// Add a control to the next position in the box, expanding to use all available space.
     pub fn add_stretchy<T: Into<Control>>(&mut self, _ctx: &UI, child: T) {
-        self.append(_ctx, child, LayoutStrategy::Stretchy);
+        let control = append(self.uiBox, _ctx, child, LayoutStrategy::Stretchy);
+        self.children.push(control);
     }
 
     /// Add a control to the end of the box, sized by the given layout strategy.
     #[deprecated(since = "0.4.0", note = "Use one of `add` or `add_stretchy` instead.")]
     pub fn append<T: Into<Control>>(&mut self, _ctx: &UI, child: T, strategy: LayoutStrategy) {
-        append(self.uiBox, _ctx, child, strategy)
+        let control = append(self.uiBox, _ctx, child, strategy);
+        self.children.push(control);
+    }
+
+    /// Add a control to the next position in the box, sized according to the given
+    /// [`Constraint`](enum.Constraint.html).
+    ///
+    /// Constrained children are laid out in an internal grid (created on first use
+    /// and appended to the box as a single stretchy child), each occupying a number
+    /// of columns proportional to its weight so that weighted children share space
+    /// in the requested proportions. See [`Constraint`](enum.Constraint.html) for
+    /// the exact resolution.
+    ///
+    /// Because every constrained child goes into that one grid, which is appended at
+    /// the position of the first constrained add, constrained and unconstrained adds
+    /// should not be interleaved: do all of a box's `add`/`add_stretchy` calls and
+    /// all of its `add_with_constraint` calls as two separate groups, or the
+    /// constrained children will render out of order relative to the plain ones.
+    pub fn add_with_constraint<T: Into<Control>>(
+        &mut self,
+        ctx: &UI,
+        child: T,
+        constraint: Constraint,
+    ) {
+        if self.constraint_grid.is_none() {
+            let mut grid = LayoutGrid::new(ctx);
+            grid.set_padded(ctx, true);
+            let grid_control =
+                unsafe { Control::from_ui_control(grid.uiGrid as *mut uiControl) };
+            unsafe {
+                assert!(ctx.parent_of(grid_control.clone()).is_none());
+                ui_sys::uiBoxAppend(self.uiBox, grid_control.ui_control, true as c_int);
+            }
+            // Track the backing grid like any other child so `delete` indices stay
+            // aligned with the box's real child list.
+            self.children.push(grid_control);
+            self.constraint_grid = Some(grid);
+        }
+        let weight = constraint.weight();
+        let expand = if constraint.is_weighted() {
+            GridExpand::Horizontal
+        } else {
+            GridExpand::Neither
+        };
+        let left = self.constraint_offset;
+        self.constraint_offset += weight;
+        let grid = self.constraint_grid.as_mut().unwrap();
+        grid.append(
+            ctx,
+            child,
+            left,
+            0,
+            weight,
+            1,
+            expand,
+            GridAlignment::Fill,
+            GridAlignment::Fill,
+        );
+    }
+
+    /// Remove and destroy the control at the given index in the box.
+    ///
+    /// Unlike the underlying `uiBoxDelete`, which only unparents the control, this
+    /// also drops the box's owned handle and destroys the control, releasing its
+    /// memory. Panics if `index` is out of range.
+    pub fn delete(&mut self, _ctx: &UI, index: usize) {
+        let control = self.children.remove(index);
+        // If the control being destroyed is the backing constraint grid, forget it
+        // so a later `add_with_constraint` builds a fresh one rather than appending
+        // into freed memory.
+        if self
+            .constraint_grid
+            .as_ref()
+            .map_or(false, |grid| grid.uiGrid as *mut uiControl == control.ui_control)
+        {
+            self.constraint_grid = None;
+            self.constraint_offset = 0;
+        }
+        unsafe {
+            ui_sys::uiBoxDelete(self.uiBox, index as c_int);
+            ui_sys::uiControlDestroy(control.ui_control);
+        }
     }
 
     /// Determine whenther the box provides padding around its children.
@@ -162,37 +505,109 @@ impl HorizontalBox {
     pub fn set_padded(&mut self, _ctx: &UI, padded: bool) {
         set_padded(self.uiBox, padded, _ctx)
     }
-}
 
-define_control! {
-    /// Group of tabs, each of which shows a different sub-control.
-    ///
-    /// # Example
+    /// Create a nested horizontal box, populate it with the given closure, then add
+    /// it to this box using the given layout strategy.
     ///
-    /// This example will create a two-tab layout with tabs entitled
-    /// "Tab One" and "Tab Two".
-    ///
-    /// ```
-    /// # use iui::prelude::*;
-    /// # use iui::controls::{Label, VerticalBox, TabGroup};
-    /// # let ui = UI::init().expect("Couldn't initialize UI library");
-    /// # let mut win = Window::new(&ui, "Test App", 200, 200, WindowType::NoMenubar);
-    /// // Create two vertical layouts
-    /// let mut vbox1 = VerticalBox::new(&ui);
-    /// let mut vbox2 = VerticalBox::new(&ui);
-    ///
-    /// // Populate vertical layouts with controls here
+    /// This lets callers build nested layouts without creating each child box,
+    /// threading `&ui` through it, and calling `add` by hand.
+    pub fn horizontal<F: FnOnce(&mut HorizontalBox)>(
+        &mut self,
+        ctx: &UI,
+        strategy: LayoutStrategy,
+        f: F,
+    ) {
+        let mut child = HorizontalBox::new(ctx);
+        f(&mut child);
+        let control = append(self.uiBox, ctx, child, strategy);
+        self.children.push(control);
+    }
+
+    /// Create a nested vertical box, populate it with the given closure, then add
+    /// it to this box using the given layout strategy.
+    pub fn vertical<F: FnOnce(&mut VerticalBox)>(
+        &mut self,
+        ctx: &UI,
+        strategy: LayoutStrategy,
+        f: F,
+    ) {
+        let mut child = VerticalBox::new(ctx);
+        f(&mut child);
+        let control = append(self.uiBox, ctx, child, strategy);
+        self.children.push(control);
+    }
+
+    /// Construct a control with the given closure and add it to the box using the
+    /// given layout strategy.
+    pub fn add_with<T: Into<Control>, F: FnOnce(&UI) -> T>(
+        &mut self,
+        ctx: &UI,
+        strategy: LayoutStrategy,
+        f: F,
+    ) {
+        let child = f(ctx);
+        let control = append(self.uiBox, ctx, child, strategy);
+        self.children.push(control);
+    }
+}
+
+/// Group of tabs, each of which shows a different sub-control.
+///
+/// # Example
+///
+/// This example will create a two-tab layout with tabs entitled
+/// "Tab One" and "Tab Two".
+///
+/// ```
+/// # use iui::prelude::*;
+/// # use iui::controls::{Label, VerticalBox, TabGroup};
+/// # let ui = UI::init().expect("Couldn't initialize UI library");
+/// # let mut win = Window::new(&ui, "Test App", 200, 200, WindowType::NoMenubar);
+/// // Create two vertical layouts
+/// let mut vbox1 = VerticalBox::new(&ui);
+/// let mut vbox2 = VerticalBox::new(&ui);
+///
+/// // Populate vertical layouts with controls here
+///
+/// // Create a TabGroup
+/// let mut tabs = TabGroup::new(&ui);
+/// tabs.append(&ui, "Tab One", vbox1);
+/// tabs.append(&ui, "Tab Two", vbox2);
+/// # win.set_child(&ui, tabs);
+/// # ui.quit();
+/// # ui.main();
+/// ```
+pub struct TabGroup {
+    uiTab: *mut uiTab,
+    /// The controls shown on each page, in page order, so they can be deleted
+    /// without leaking.
+    children: Vec<Control>,
+}
+
+// `VerticalBox`, `HorizontalBox`, and `TabGroup` deliberately do not implement
+// `Clone`: each owns the controls tracked in `children` and destroys them in
+// `delete`, so copying the raw pointer and the handles would give two owners that
+// would each try to free the same controls, double-freeing them.
+
+impl TabGroup {
+    /// Create a `TabGroup` from a raw pointer to the underlying `uiTab`.
     ///
-    /// // Create a TabGroup
-    /// let mut tabs = TabGroup::new(&ui);
-    /// tabs.append(&ui, "Tab One", vbox1);
-    /// tabs.append(&ui, "Tab Two", vbox2);
-    /// # win.set_child(&ui, tabs);
-    /// # ui.quit();
-    /// # ui.main();
-    /// ```
-    rust_type: TabGroup,
-    sys_type: uiTab
+    /// The returned group starts with an empty child list, so the page count and
+    /// `append`/`insert_at`/`delete` indices are tracked from zero. The raw tab must
+    /// therefore have no existing pages, or that bookkeeping will desync from
+    /// libui's real page count.
+    pub unsafe fn from_raw(raw: *mut uiTab) -> TabGroup {
+        TabGroup {
+            uiTab: raw,
+            children: Vec::new(),
+        }
+    }
+}
+
+impl From<TabGroup> for Control {
+    fn from(t: TabGroup) -> Control {
+        unsafe { Control::from_ui_control(t.uiTab as *mut uiControl) }
+    }
 }
 
 impl TabGroup {
@@ -209,8 +624,9 @@ impl TabGroup {
         unsafe {
             let c_string = CString::new(name.as_bytes().to_vec()).unwrap();
             ui_sys::uiTabAppend(self.uiTab, c_string.as_ptr(), control.ui_control);
-            ui_sys::uiTabNumPages(self.uiTab) as u64
         }
+        self.children.push(control);
+        self.children.len() as u64
     }
 
     /// Add the given control before the given index in the tab group, as a new tab with a given name.
@@ -223,30 +639,35 @@ impl TabGroup {
         before: u64,
         control: T,
     ) -> u64 {
+        let control = control.into();
+        // Clamp to the current page count so an out-of-range `before` appends to
+        // the end instead of panicking on the `Vec::insert`; this mirrors how the
+        // underlying `uiTabInsertAt` tolerates an index at the end of the group.
+        let before = before.min(self.children.len() as u64);
         unsafe {
             let c_string = CString::new(name.as_bytes().to_vec()).unwrap();
-            ui_sys::uiTabInsertAt(
-                self.uiTab,
-                c_string.as_ptr(),
-                before,
-                control.into().ui_control,
-            );
-            ui_sys::uiTabNumPages(self.uiTab) as u64
+            ui_sys::uiTabInsertAt(self.uiTab, c_string.as_ptr(), before, control.ui_control);
         }
+        self.children.insert(before as usize, control);
+        self.children.len() as u64
     }
 
-    /// Remove the control at the given index in the tab group.
+    /// Remove and destroy the control at the given index in the tab group.
     ///
     /// Returns the number of tabs in the group after removing the tab, or an error if that index was out of bounds.
     ///
-    /// NOTE: This will leak the deleted control! We have no way of actually getting it
-    /// to decrement its reference count per `libui`'s UI as of today, unless we maintain a
-    /// separate list of children ourselves…
+    /// Because the tab group owns a handle to each of its controls, removing one
+    /// drops that handle and destroys the control, releasing its memory instead of
+    /// leaking it as earlier versions did.
     pub fn delete(&mut self, _ctx: &UI, index: u64) -> Result<u64, UIError> {
-        let n = unsafe { ui_sys::uiTabNumPages(self.uiTab) as u64 };
+        let n = self.children.len() as u64;
         if index < n {
-            unsafe { ui_sys::uiTabDelete(self.uiTab, index) };
-            Ok(n)
+            let control = self.children.remove(index as usize);
+            unsafe {
+                ui_sys::uiTabDelete(self.uiTab, index);
+                ui_sys::uiControlDestroy(control.ui_control);
+            }
+            Ok(self.children.len() as u64)
         } else {
             Err(UIError::TabGroupIndexOutOfBounds { index: index, n: n })
         }
@@ -263,43 +684,65 @@ impl TabGroup {
     }
 }
 
-define_control! {
-    /// Collects controls together, with (optionally) a margin and/or title.
-    ///
-    /// # Example
-    ///
-    /// This example will produce two labels grouped together and titled
-    /// "Status".
-    ///
-    /// ```
-    /// # use iui::prelude::*;
-    /// # use iui::controls::{Label, VerticalBox, Group};
-    /// # let ui = UI::init().expect("Couldn't initialize UI library");
-    /// # let mut win = Window::new(&ui, "Test App", 200, 200, WindowType::NoMenubar);
-    /// // Create a vertical layout
-    /// let mut vbox = VerticalBox::new(&ui);
-    ///
-    /// // Create labels
-    /// let cpu_label = Label::new(&ui, "CPU: 10%");
-    /// let ram_label = Label::new(&ui, "RAM: 1.2 GiB");
-    /// let swp_label = Label::new(&ui, "SWP: 0.9 GiB");
-    ///
-    /// // Place labels in the vertical layout
-    /// vbox.add(&ui, cpu_label);
-    /// vbox.add(&ui, ram_label);
-    /// vbox.add(&ui, swp_label);
-    ///
-    /// // Create a Group
-    /// let mut group = Group::new(&ui, "Usage");
-    ///
-    /// // Set the vbox as the child of the group, containing it.
-    /// group.set_child(&ui, vbox);
-    /// # win.set_child(&ui, group);
-    /// # ui.quit();
-    /// # ui.main();
-    /// ```
-    rust_type: Group,
-    sys_type: uiGroup
+/// Collects controls together, with (optionally) a margin and/or title.
+///
+/// # Example
+///
+/// This example will produce two labels grouped together and titled
+/// "Status".
+///
+/// ```
+/// # use iui::prelude::*;
+/// # use iui::controls::{Label, VerticalBox, Group};
+/// # let ui = UI::init().expect("Couldn't initialize UI library");
+/// # let mut win = Window::new(&ui, "Test App", 200, 200, WindowType::NoMenubar);
+/// // Create a vertical layout
+/// let mut vbox = VerticalBox::new(&ui);
+///
+/// // Create labels
+/// let cpu_label = Label::new(&ui, "CPU: 10%");
+/// let ram_label = Label::new(&ui, "RAM: 1.2 GiB");
+/// let swp_label = Label::new(&ui, "SWP: 0.9 GiB");
+///
+/// // Place labels in the vertical layout
+/// vbox.add(&ui, cpu_label);
+/// vbox.add(&ui, ram_label);
+/// vbox.add(&ui, swp_label);
+///
+/// // Create a Group
+/// let mut group = Group::new(&ui, "Usage");
+///
+/// // Set the vbox as the child of the group, containing it.
+/// group.set_child(&ui, vbox);
+/// # win.set_child(&ui, group);
+/// # ui.quit();
+/// # ui.main();
+/// ```
+pub struct Group {
+    uiGroup: *mut uiGroup,
+    /// The group's current child, if any, so it can be replaced or destroyed
+    /// without leaking.
+    child: Option<Control>,
+}
+
+// Like the box and tab types, `Group` owns its child and destroys it when it is
+// replaced in `set_child`, so it is intentionally not `Clone`: sharing the owned
+// handle would let two owners free the same control.
+
+impl Group {
+    /// Create a `Group` from a raw pointer to the underlying `uiGroup`.
+    pub unsafe fn from_raw(raw: *mut uiGroup) -> Group {
+        Group {
+            uiGroup: raw,
+            child: None,
+        }
+    }
+}
+
+impl From<Group> for Control {
+    fn from(g: Group) -> Control {
+        unsafe { Control::from_ui_control(g.uiGroup as *mut uiControl) }
+    }
 }
 
 impl Group {
@@ -335,9 +778,14 @@ impl Group {
         }
     }
 
-    // Set the group's child widget.
+    // Set the group's child widget, destroying any previously-set child so it is
+    // not leaked.
     pub fn set_child<T: Into<Control>>(&mut self, _ctx: &UI, child: T) {
-        unsafe { ui_sys::uiGroupSetChild(self.uiGroup, child.into().ui_control) }
+        let child = child.into();
+        unsafe { ui_sys::uiGroupSetChild(self.uiGroup, child.ui_control) }
+        if let Some(old) = self.child.replace(child) {
+            unsafe { ui_sys::uiControlDestroy(old.ui_control) }
+        }
     }
 
     // Check whether or not the group draws a margin.
@@ -377,6 +825,7 @@ impl Spacer {
 
 /// Informs a `LayoutGrid` about how a control should use available space
 /// in one or both dimensions.
+#[derive(Clone, Copy, PartialEq)]
 pub enum GridExpand {
     /// This control should not use extra space
     Neither,
@@ -544,3 +993,275 @@ impl LayoutGrid {
         }
     }
 }
+
+/// Lays out label/control pairs as a two-column "settings form".
+///
+/// Each row places a label in the first column, aligned according to
+/// [`set_label_alignment`](struct.FormGroup.html#method.set_label_alignment),
+/// and its widget in the second column, which expands to fill the available
+/// horizontal space. Internally this is a [`LayoutGrid`](struct.LayoutGrid.html),
+/// so adding a `FormGroup` to a window or box works like any other control.
+///
+/// # Example
+///
+/// ```
+/// # use iui::prelude::*;
+/// # use iui::controls::{Entry, FormGroup};
+/// # let ui = UI::init().expect("Couldn't initialize UI library");
+/// # let mut win = Window::new(&ui, "Test App", 200, 200, WindowType::NoMenubar);
+/// let mut form = FormGroup::new(&ui);
+/// form.add_widget(&ui, "Name", Entry::new(&ui));
+/// form.add_widget(&ui, "Email", Entry::new(&ui));
+/// # win.set_child(&ui, form);
+/// # ui.quit();
+/// # ui.main();
+/// ```
+pub struct FormGroup {
+    grid: LayoutGrid,
+    rows: i32,
+    label_alignment: GridAlignment,
+}
+
+impl FormGroup {
+    /// Create a new, empty form.
+    pub fn new(ctx: &UI) -> FormGroup {
+        let mut grid = LayoutGrid::new(ctx);
+        grid.set_padded(ctx, true);
+        FormGroup {
+            grid,
+            rows: 0,
+            label_alignment: GridAlignment::End,
+        }
+    }
+
+    /// Add a labelled control as a new row at the bottom of the form.
+    ///
+    /// The label is aligned according to the current label alignment; changing
+    /// the alignment afterwards only affects rows added later.
+    pub fn add_widget<T: Into<Control>>(&mut self, ctx: &UI, label: &str, control: T) {
+        let row = self.rows;
+        let label = Label::new(ctx, label);
+        self.grid.append(
+            ctx,
+            label,
+            0,
+            row,
+            1,
+            1,
+            GridExpand::Neither,
+            self.label_alignment,
+            GridAlignment::Center,
+        );
+        self.grid.append(
+            ctx,
+            control,
+            1,
+            row,
+            1,
+            1,
+            GridExpand::Horizontal,
+            GridAlignment::Fill,
+            GridAlignment::Center,
+        );
+        self.rows += 1;
+    }
+
+    /// Set the horizontal alignment used for the label column of rows added after
+    /// this call. Defaults to [`GridAlignment::End`](enum.GridAlignment.html) so
+    /// labels sit flush against their widgets.
+    pub fn set_label_alignment(&mut self, _ctx: &UI, alignment: GridAlignment) {
+        self.label_alignment = alignment;
+    }
+
+    /// Set whether or not the form provides padding between the label and control
+    /// columns and between rows.
+    pub fn set_padded(&mut self, ctx: &UI, padded: bool) {
+        self.grid.set_padded(ctx, padded);
+    }
+
+    /// Set whether a margin is drawn between each label and its control.
+    ///
+    /// libui grids expose only a single on/off padding flag rather than an
+    /// adjustable per-column margin, so this is deliberately a boolean toggle (not a
+    /// pixel distance) and shares its state with
+    /// [`set_padded`](struct.FormGroup.html#method.set_padded); it is kept as a
+    /// separate name for callers who think of the gap in terms of the label column.
+    pub fn set_label_margin(&mut self, ctx: &UI, margined: bool) {
+        self.grid.set_padded(ctx, margined);
+    }
+}
+
+impl From<FormGroup> for Control {
+    fn from(form: FormGroup) -> Control {
+        form.grid.into()
+    }
+}
+
+/// Declaratively builds a [`LayoutGrid`](struct.LayoutGrid.html) from a 2-D array
+/// of controls, computing each cell's `left`/`top` coordinates and spans so callers
+/// never have to track them by hand.
+///
+/// Configure the per-column expansion and alignment policies (and any per-cell span
+/// overrides) first, then hand the rows to
+/// [`with_rows`](struct.TableLayout.html#method.with_rows), which issues the
+/// `uiGridAppend` calls. The finished `TableLayout` can be added to a window or box
+/// like any other control.
+///
+/// # Example
+///
+/// ```
+/// # use iui::prelude::*;
+/// # use iui::controls::{Label, Entry, TableLayout, GridExpand};
+/// # let ui = UI::init().expect("Couldn't initialize UI library");
+/// # let mut win = Window::new(&ui, "Test App", 200, 200, WindowType::NoMenubar);
+/// let table = TableLayout::new(&ui)
+///     .padded(&ui, true)
+///     .set_column_expand(&ui, 1, GridExpand::Horizontal)
+///     .with_rows(&ui, vec![
+///         vec![Label::new(&ui, "Host").into(), Entry::new(&ui).into()],
+///         vec![Label::new(&ui, "Port").into(), Entry::new(&ui).into()],
+///     ]);
+/// # win.set_child(&ui, table);
+/// # ui.quit();
+/// # ui.main();
+/// ```
+pub struct TableLayout {
+    grid: LayoutGrid,
+    column_expand: Vec<GridExpand>,
+    column_alignment: Vec<GridAlignment>,
+    spans: Vec<(usize, usize, i32, i32)>,
+}
+
+impl TableLayout {
+    /// Create a new, empty table builder.
+    pub fn new(ctx: &UI) -> TableLayout {
+        let mut grid = LayoutGrid::new(ctx);
+        grid.set_padded(ctx, true);
+        TableLayout {
+            grid,
+            column_expand: Vec::new(),
+            column_alignment: Vec::new(),
+            spans: Vec::new(),
+        }
+    }
+
+    /// Set whether the table pads the space between its cells.
+    ///
+    /// libui's grid only exposes a single on/off padding flag rather than
+    /// independent horizontal and vertical spacing, so this is a boolean toggle
+    /// rather than a pair of pixel distances.
+    pub fn padded(mut self, ctx: &UI, padded: bool) -> TableLayout {
+        self.grid.set_padded(ctx, padded);
+        self
+    }
+
+    /// Set how the given column uses extra horizontal and vertical space. Columns
+    /// default to [`GridExpand::Neither`](enum.GridExpand.html).
+    pub fn set_column_expand(mut self, _ctx: &UI, col: usize, expand: GridExpand) -> TableLayout {
+        if col >= self.column_expand.len() {
+            self.column_expand.resize_with(col + 1, || GridExpand::Neither);
+        }
+        self.column_expand[col] = expand;
+        self
+    }
+
+    /// Set how controls in the given column are aligned. Columns default to
+    /// [`GridAlignment::Fill`](enum.GridAlignment.html).
+    pub fn set_column_alignment(
+        mut self,
+        _ctx: &UI,
+        col: usize,
+        alignment: GridAlignment,
+    ) -> TableLayout {
+        if col >= self.column_alignment.len() {
+            self.column_alignment
+                .resize_with(col + 1, || GridAlignment::Fill);
+        }
+        self.column_alignment[col] = alignment;
+        self
+    }
+
+    /// Make the control at the given `(col, row)` cell occupy `xspan` columns and
+    /// `yspan` rows. Without an override each cell occupies a single cell.
+    ///
+    /// `col` and `row` index the control within the rows passed to
+    /// [`with_rows`](struct.TableLayout.html#method.with_rows); later controls are
+    /// moved past the grid cells the span covers.
+    pub fn set_span(
+        mut self,
+        _ctx: &UI,
+        col: usize,
+        row: usize,
+        xspan: i32,
+        yspan: i32,
+    ) -> TableLayout {
+        self.spans.push((col, row, xspan, yspan));
+        self
+    }
+
+    /// Append the given rows of controls to the grid, placing each at its computed
+    /// coordinates with the configured per-column policies and any span overrides.
+    pub fn with_rows(mut self, ctx: &UI, rows: Vec<Vec<Control>>) -> TableLayout {
+        // Grid cells already covered by an earlier control's span.
+        let mut covered: Vec<(i32, i32)> = Vec::new();
+        for (row_idx, row) in rows.into_iter().enumerate() {
+            let top = row_idx as i32;
+            let mut left = 0;
+            for (col_idx, control) in row.into_iter().enumerate() {
+                while covered.contains(&(left, top)) {
+                    left += 1;
+                }
+                let (xspan, yspan) = self.span_for(col_idx, row_idx);
+                let expand = self.expand_for(left as usize);
+                let halign = self.alignment_for(left as usize);
+                self.grid.append(
+                    ctx,
+                    control,
+                    left,
+                    top,
+                    xspan,
+                    yspan,
+                    expand,
+                    halign,
+                    GridAlignment::Fill,
+                );
+                for dx in 0..xspan {
+                    for dy in 0..yspan {
+                        covered.push((left + dx, top + dy));
+                    }
+                }
+                left += xspan;
+            }
+        }
+        self
+    }
+
+    fn expand_for(&self, col: usize) -> GridExpand {
+        self.column_expand
+            .get(col)
+            .copied()
+            .unwrap_or(GridExpand::Neither)
+    }
+
+    fn alignment_for(&self, col: usize) -> GridAlignment {
+        self.column_alignment
+            .get(col)
+            .copied()
+            .unwrap_or(GridAlignment::Fill)
+    }
+
+    fn span_for(&self, col: usize, row: usize) -> (i32, i32) {
+        for &(c, r, xspan, yspan) in &self.spans {
+            if c == col && r == row {
+                return (xspan, yspan);
+            }
+        }
+        (1, 1)
+    }
+}
+
+impl From<TableLayout> for Control {
+    fn from(table: TableLayout) -> Control {
+        table.grid.into()
+    }
+}